@@ -1,5 +1,7 @@
 use std::path::{Path, PathBuf};
-use std::fs::{remove_dir_all, read_dir};
+use std::fs::{remove_dir_all, read_dir, metadata, symlink_metadata, create_dir, create_dir_all, ReadDir};
+use std::fs::copy as fs_copy;
+use std::fs::read_link as fs_read_link;
 use std::io::Result as IoResult;
 
 /// Returns true if `path` exists and is a directory.
@@ -30,6 +32,10 @@ pub fn children<P: AsRef<Path>>(path: P) -> IoResult<::std::vec::IntoIter<PathBu
 
 /// Returns an iterator of the child files in `path`.
 ///
+/// Symlinks are not followed, so a link to a file is not included. Use
+/// `files_following_symlinks` to classify entries by their link target
+/// instead.
+///
 /// ```rust,no_run
 /// for file in latin::directory::files("./").unwrap() {
 ///     for (i, line) in latin::file::read_lines(file).unwrap().enumerate() {
@@ -38,12 +44,35 @@ pub fn children<P: AsRef<Path>>(path: P) -> IoResult<::std::vec::IntoIter<PathBu
 /// }
 /// ```
 pub fn files<P: AsRef<Path>>(path: P) -> IoResult<::std::vec::IntoIter<PathBuf>> {
+    files_impl(path, false)
+}
+
+/// Returns an iterator of the child files in `path`, following symlinks.
+///
+/// A symlink whose target is a file is included, classified by the target
+/// rather than the link itself.
+///
+/// ```rust,no_run
+/// for file in latin::directory::files_following_symlinks("./").unwrap() {
+///     println!("{:?}", file);
+/// }
+/// ```
+pub fn files_following_symlinks<P: AsRef<Path>>(path: P) -> IoResult<::std::vec::IntoIter<PathBuf>> {
+    files_impl(path, true)
+}
+
+fn files_impl<P: AsRef<Path>>(path: P, follow_symlinks: bool) -> IoResult<::std::vec::IntoIter<PathBuf>> {
     let mut out = vec![];
     for entry in try!(read_dir(path)) {
         let entry = try!(entry);
 
-        // TODO: also check symlinks
-        if try!(entry.file_type()).is_file() { 
+        let is_file = if follow_symlinks {
+            try!(metadata(entry.path())).is_file()
+        } else {
+            try!(entry.file_type()).is_file()
+        };
+
+        if is_file {
             out.push(entry.path());
         }
     }
@@ -52,18 +81,45 @@ pub fn files<P: AsRef<Path>>(path: P) -> IoResult<::std::vec::IntoIter<PathBuf>>
 
 /// Returns a list of all the subdirectories in `path`.
 ///
+/// Symlinks are not followed, so a link to a directory is not included. Use
+/// `sub_directories_following_symlinks` to classify entries by their link
+/// target instead.
+///
 /// ```rust,no_run
 /// for subdir in latin::directory::sub_directories("./").unwrap() {
 ///     let children_count = latin::directory::children(subdir).unwrap().count();
 ///     println!("{:?}: {}", subdir, children_count);
 /// }
 pub fn sub_directories<P: AsRef<Path>>(path: P) -> IoResult<::std::vec::IntoIter<PathBuf>> {
+    sub_directories_impl(path, false)
+}
+
+/// Returns a list of all the subdirectories in `path`, following symlinks.
+///
+/// A symlink whose target is a directory is included, classified by the
+/// target rather than the link itself.
+///
+/// ```rust,no_run
+/// for subdir in latin::directory::sub_directories_following_symlinks("./").unwrap() {
+///     println!("{:?}", subdir);
+/// }
+/// ```
+pub fn sub_directories_following_symlinks<P: AsRef<Path>>(path: P) -> IoResult<::std::vec::IntoIter<PathBuf>> {
+    sub_directories_impl(path, true)
+}
+
+fn sub_directories_impl<P: AsRef<Path>>(path: P, follow_symlinks: bool) -> IoResult<::std::vec::IntoIter<PathBuf>> {
     let mut out = vec![];
     for entry in try!(read_dir(path)) {
         let entry = try!(entry);
 
-        // TODO: also check symlinks
-        if try!(entry.file_type()).is_dir() {
+        let is_dir = if follow_symlinks {
+            try!(metadata(entry.path())).is_dir()
+        } else {
+            try!(entry.file_type()).is_dir()
+        };
+
+        if is_dir {
             out.push(entry.path());
         }
     }
@@ -78,3 +134,394 @@ pub fn sub_directories<P: AsRef<Path>>(path: P) -> IoResult<::std::vec::IntoIter
 pub fn remove<P: AsRef<Path>>(path: P) -> IoResult<()> {
     remove_dir_all(path)
 }
+
+/// Creates a new, empty directory at `path`.
+///
+/// An error is returned if the parent directory does not exist. Use
+/// `create_all` if the missing parent components should be created too.
+///
+/// ```rust,no_run
+/// latin::directory::create("/tmp/foobar");
+/// ```
+pub fn create<P: AsRef<Path>>(path: P) -> IoResult<()> {
+    create_dir(path)
+}
+
+/// Creates a directory at `path`, along with any missing parent directories.
+///
+/// Succeeds without doing anything if the directory already exists.
+///
+/// ```rust,no_run
+/// latin::directory::create_all("/tmp/foo/bar/baz");
+/// ```
+pub fn create_all<P: AsRef<Path>>(path: P) -> IoResult<()> {
+    create_dir_all(path)
+}
+
+/// Controls which entries a `Walk` yields.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum WalkKind {
+    /// Yield only regular files.
+    Files,
+    /// Yield only directories.
+    Directories,
+    /// Yield both files and directories.
+    Both,
+}
+
+/// A lazy, recursive iterator over the descendants of a directory.
+///
+/// Created by `directory::walk`. Holds a stack of `ReadDir` handles, one per
+/// level currently being descended, so entries are produced without ever
+/// collecting the whole tree into memory up front.
+pub struct Walk {
+    stack: Vec<ReadDir>,
+    max_depth: Option<usize>,
+    kind: WalkKind,
+}
+
+impl Walk {
+    /// Limits how many directory levels below the root are descended into.
+    ///
+    /// A depth of `0` only yields the entries directly inside the root.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Restricts the walk to only files, only directories, or both (the default).
+    pub fn kind(mut self, kind: WalkKind) -> Self {
+        self.kind = kind;
+        self
+    }
+}
+
+impl Iterator for Walk {
+    type Item = IoResult<PathBuf>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let entry = match self.stack.last_mut() {
+                Some(read_dir) => read_dir.next(),
+                None => return None,
+            };
+
+            let entry = match entry {
+                Some(entry) => entry,
+                None => {
+                    self.stack.pop();
+                    continue;
+                }
+            };
+
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => return Some(Err(e)),
+            };
+
+            let file_type = match entry.file_type() {
+                Ok(file_type) => file_type,
+                Err(e) => return Some(Err(e)),
+            };
+
+            let is_dir = file_type.is_dir();
+            let path = entry.path();
+
+            if is_dir && self.max_depth.map_or(true, |depth| self.stack.len() <= depth) {
+                match read_dir(&path) {
+                    Ok(read_dir) => self.stack.push(read_dir),
+                    Err(e) => return Some(Err(e)),
+                }
+            }
+
+            let yield_entry = match self.kind {
+                WalkKind::Files => file_type.is_file(),
+                WalkKind::Directories => is_dir,
+                WalkKind::Both => true,
+            };
+
+            if yield_entry {
+                return Some(Ok(path));
+            }
+        }
+    }
+}
+
+/// Returns a lazy iterator over every descendant of `path`, recursing into
+/// subdirectories as it goes.
+///
+/// By default every file and directory is yielded, depth is unbounded, and
+/// a per-entry error (such as a permission-denied subdirectory) is yielded
+/// in place rather than aborting the whole walk. Use `Walk::max_depth` and
+/// `Walk::kind` to narrow this down.
+///
+/// ```rust,no_run
+/// for entry in latin::directory::walk("./").unwrap() {
+///     println!("{:?}", entry.unwrap());
+/// }
+/// ```
+pub fn walk<P: AsRef<Path>>(path: P) -> IoResult<Walk> {
+    let read_dir = try!(read_dir(path));
+    Ok(Walk {
+        stack: vec![read_dir],
+        max_depth: None,
+        kind: WalkKind::Both,
+    })
+}
+
+/// Returns a lazy iterator over every regular file beneath `path`, recursing
+/// into subdirectories as it goes.
+///
+/// ```rust,no_run
+/// for file in latin::directory::walk_files("./").unwrap() {
+///     println!("{:?}", file.unwrap());
+/// }
+/// ```
+pub fn walk_files<P: AsRef<Path>>(path: P) -> IoResult<Walk> {
+    Ok(try!(walk(path)).kind(WalkKind::Files))
+}
+
+/// A single token inside a glob pattern component.
+enum GlobToken {
+    Literal(char),
+    Any,
+    Star,
+    Class(Vec<(char, char)>),
+}
+
+/// Splits a single path component of a glob pattern (no `/`) into tokens,
+/// so that a `[a-z]` character class is treated as one unit rather than
+/// several individual characters.
+fn tokenize_component(pattern: &str) -> Vec<GlobToken> {
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '?' => {
+                tokens.push(GlobToken::Any);
+                i += 1;
+            }
+            '*' => {
+                tokens.push(GlobToken::Star);
+                i += 1;
+            }
+            '[' => {
+                let mut ranges = vec![];
+                i += 1;
+                while i < chars.len() && chars[i] != ']' {
+                    if i + 2 < chars.len() && chars[i + 1] == '-' && chars[i + 2] != ']' {
+                        ranges.push((chars[i], chars[i + 2]));
+                        i += 3;
+                    } else {
+                        ranges.push((chars[i], chars[i]));
+                        i += 1;
+                    }
+                }
+                i += 1;
+                tokens.push(GlobToken::Class(ranges));
+            }
+            c => {
+                tokens.push(GlobToken::Literal(c));
+                i += 1;
+            }
+        }
+    }
+    tokens
+}
+
+fn glob_token_matches(token: &GlobToken, c: char) -> bool {
+    match *token {
+        GlobToken::Literal(l) => l == c,
+        GlobToken::Any => true,
+        GlobToken::Star => false,
+        GlobToken::Class(ref ranges) => ranges.iter().any(|&(start, end)| c >= start && c <= end),
+    }
+}
+
+/// Matches a single path component (no `/`) against a single glob pattern
+/// component using classic backtracking: advance both cursors on a
+/// literal/`?`/class match, and on `*` remember the current position so a
+/// later mismatch can resume by consuming one more character of text.
+fn glob_match_component(pattern: &str, text: &str) -> bool {
+    let tokens = tokenize_component(pattern);
+    let text: Vec<char> = text.chars().collect();
+
+    let mut pi = 0;
+    let mut ti = 0;
+    let mut star_pi: Option<usize> = None;
+    let mut star_ti = 0;
+
+    loop {
+        let at_star = match tokens.get(pi) {
+            Some(&GlobToken::Star) => true,
+            _ => false,
+        };
+
+        if !at_star && pi < tokens.len() && ti < text.len() && glob_token_matches(&tokens[pi], text[ti]) {
+            pi += 1;
+            ti += 1;
+            continue;
+        }
+
+        if at_star {
+            star_pi = Some(pi);
+            star_ti = ti;
+            pi += 1;
+            continue;
+        }
+
+        if pi == tokens.len() && ti == text.len() {
+            return true;
+        }
+
+        match star_pi {
+            Some(spi) => {
+                star_ti += 1;
+                if star_ti > text.len() {
+                    return false;
+                }
+                pi = spi + 1;
+                ti = star_ti;
+            }
+            None => return false,
+        }
+    }
+}
+
+/// Matches a sequence of path components against a sequence of glob pattern
+/// components. A `**` pattern component branches: either it consumes zero
+/// path components (and the rest of the pattern is matched against the same
+/// remaining path), or it consumes one component and `**` is retried against
+/// the tail, allowing it to match at any depth.
+fn glob_match_components(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.first() {
+        None => path.is_empty(),
+        Some(&"**") => {
+            if glob_match_components(&pattern[1..], path) {
+                return true;
+            }
+            match path.split_first() {
+                Some((_, rest)) => glob_match_components(pattern, rest),
+                None => false,
+            }
+        }
+        Some(component) => {
+            match path.split_first() {
+                Some((first, rest)) => {
+                    glob_match_component(component, first) && glob_match_components(&pattern[1..], rest)
+                }
+                None => false,
+            }
+        }
+    }
+}
+
+/// Returns every path that matches the shell-style glob `pattern`.
+///
+/// Supports `?` (any single character within a component), `*` (any run of
+/// characters within a component), `[abc]`/`[a-z]` character classes, and
+/// `**` (zero or more whole path components, for recursive descent).
+///
+/// The literal directories at the start of `pattern` (up to the first
+/// component containing a wildcard) are used as the starting directory;
+/// only the tree below it is walked on disk, and each entry's path
+/// relative to it is matched component-by-component against the rest of
+/// the pattern. A pattern with no wildcard yields that path if it exists.
+///
+/// ```rust,no_run
+/// for path in latin::directory::glob("src/**/*.rs").unwrap() {
+///     println!("{:?}", path);
+/// }
+/// ```
+pub fn glob<S: AsRef<str>>(pattern: S) -> IoResult<::std::vec::IntoIter<PathBuf>> {
+    let pattern = pattern.as_ref();
+    let components: Vec<&str> = pattern.split('/').filter(|c| !c.is_empty()).collect();
+
+    let mut root = if pattern.starts_with('/') { PathBuf::from("/") } else { PathBuf::new() };
+    let mut pattern_start = 0;
+    for component in &components {
+        if component.contains('*') || component.contains('?') || component.contains('[') {
+            break;
+        }
+        root.push(component);
+        pattern_start += 1;
+    }
+    if pattern_start == components.len() {
+        let out = if !root.as_os_str().is_empty() && root.exists() { vec![root] } else { vec![] };
+        return Ok(out.into_iter());
+    }
+    if root.as_os_str().is_empty() {
+        root = PathBuf::from(".");
+    }
+
+    let pattern_components = &components[pattern_start..];
+
+    let mut out = vec![];
+    for entry in try!(walk(&root)) {
+        let entry = try!(entry);
+
+        let relative = match entry.strip_prefix(&root) {
+            Ok(relative) => relative,
+            Err(_) => continue,
+        };
+
+        let entry_components: Vec<&str> = relative.components()
+            .filter_map(|c| c.as_os_str().to_str())
+            .collect();
+
+        if glob_match_components(pattern_components, &entry_components) {
+            out.push(entry);
+        }
+    }
+    Ok(out.into_iter())
+}
+
+/// Recursively copies the directory tree rooted at `from` into `to`.
+///
+/// `to` (and any missing parent directories) is created if it does not
+/// already exist. Every file beneath `from` is copied to the equivalent
+/// relative path beneath `to` via `std::fs::copy`, and every subdirectory
+/// is recreated with `create_all`.
+///
+/// If `preserve_symlinks` is true, a symlink beneath `from` is recreated
+/// as a symlink at the destination rather than having its target copied.
+/// Otherwise a symlink to a file has its target's contents copied, while a
+/// symlink to a directory is not descended into and is recreated as an
+/// empty directory.
+///
+/// Copying stops at the first failure, which is returned as the error.
+///
+/// ```rust,no_run
+/// latin::directory::copy("./src", "./backup/src", false);
+/// ```
+pub fn copy<Fp: AsRef<Path>, Tp: AsRef<Path>>(from: Fp, to: Tp, preserve_symlinks: bool) -> IoResult<()> {
+    let from = from.as_ref();
+    let to = to.as_ref();
+
+    try!(create_dir_all(to));
+
+    for entry in try!(walk(from)) {
+        let entry = try!(entry);
+
+        let relative = match entry.strip_prefix(from) {
+            Ok(relative) => relative,
+            Err(_) => continue,
+        };
+        let dest = to.join(relative);
+
+        if preserve_symlinks && try!(symlink_metadata(&entry)).file_type().is_symlink() {
+            let target = try!(fs_read_link(&entry));
+            try!(::file::symlink(target, &dest));
+            continue;
+        }
+
+        if try!(metadata(&entry)).is_dir() {
+            try!(create_dir_all(&dest));
+        } else {
+            try!(fs_copy(&entry, &dest));
+        }
+    }
+
+    Ok(())
+}
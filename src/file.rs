@@ -3,8 +3,12 @@ use std::iter::IntoIterator;
 use std::path::Path;
 use std::io::{Result as IoResult, Error as IoError};
 use std::io::{Write, Read, BufRead, Lines, BufReader};
-use std::fs::{OpenOptions, File, remove_file};
+use std::fs::{OpenOptions, File, remove_file, rename, metadata, symlink_metadata};
 use std::fs::copy as fs_copy;
+use std::fs::read_link as fs_read_link;
+use std::path::PathBuf;
+use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 #[cfg(windows)]
 const LINE_SEP: &'static [u8] = b"\r\n";
@@ -47,6 +51,89 @@ pub fn write_lines<P: AsRef<Path>, I: IntoIterator<Item=B, IntoIter=A>, A: Itera
     Ok(())
 }
 
+/// Returns a sibling path to use as a temporary file while atomically
+/// writing to `path`, named after the target plus a pid/timestamp suffix
+/// to avoid colliding with concurrent writers.
+fn temp_sibling_path(path: &Path) -> PathBuf {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+
+    let mut file_name = path.file_name().map(|n| n.to_os_string()).unwrap_or_default();
+    file_name.push(format!(".{}.{}.tmp", process::id(), nanos));
+    path.with_file_name(file_name)
+}
+
+/// Writes `content` into a file at `path` atomically.
+///
+/// The contents are written to a temporary file in the same directory,
+/// flushed and synced to disk, then moved into place with a single
+/// `rename`. Since renaming within a filesystem is atomic, any reader
+/// sees either the old file or the complete new one, never a partial
+/// write. The temporary file is removed if the write or rename fails.
+///
+/// ```rust,no_run
+/// latin::file::write_atomic("./foo.txt", "contents");
+/// ```
+pub fn write_atomic<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C) -> IoResult<()> {
+    let path = path.as_ref();
+    let temp_path = temp_sibling_path(path);
+
+    if let Err(e) = write_atomic_impl(&temp_path, contents.as_ref()) {
+        let _ = remove_file(&temp_path);
+        return Err(e);
+    }
+
+    if let Err(e) = rename(&temp_path, path) {
+        let _ = remove_file(&temp_path);
+        return Err(e);
+    }
+
+    Ok(())
+}
+
+fn write_atomic_impl(temp_path: &Path, contents: &[u8]) -> IoResult<()> {
+    let mut file = try!(OpenOptions::new().write(true).create(true).truncate(true).open(temp_path));
+    try!(file.write_all(contents));
+    file.sync_all()
+}
+
+/// Writes lines into a file at `path` atomically.
+///
+/// Works like `write_atomic`, but writes one line per item in `lines`,
+/// each followed by the platform line separator, before the temporary
+/// file is renamed into place.
+///
+/// ```rust,no_run
+/// latin::file::write_lines_atomic("./foo.txt", vec!["line1", "line2"]);
+/// ```
+pub fn write_lines_atomic<P: AsRef<Path>, I: IntoIterator<Item=B, IntoIter=A>, A: Iterator<Item=B>, B: AsRef<[u8]>>(path: P, lines: I) -> IoResult<()> {
+    let path = path.as_ref();
+    let temp_path = temp_sibling_path(path);
+
+    if let Err(e) = write_lines_atomic_impl(&temp_path, lines) {
+        let _ = remove_file(&temp_path);
+        return Err(e);
+    }
+
+    if let Err(e) = rename(&temp_path, path) {
+        let _ = remove_file(&temp_path);
+        return Err(e);
+    }
+
+    Ok(())
+}
+
+fn write_lines_atomic_impl<I: IntoIterator<Item=B, IntoIter=A>, A: Iterator<Item=B>, B: AsRef<[u8]>>(temp_path: &Path, lines: I) -> IoResult<()> {
+    let mut file = try!(OpenOptions::new().write(true).create(true).truncate(true).open(temp_path));
+    for line in lines.into_iter() {
+        try!(file.write_all(line.as_ref()));
+        try!(file.write_all(LINE_SEP));
+    }
+    file.sync_all()
+}
+
 /// Appends some contents to the file at `path`.
 ///
 /// If the file at `path` does not exist, it will be created.
@@ -188,6 +275,113 @@ pub fn remove<P: AsRef<Path>>(path: P) -> IoResult<()> {
     remove_file(path)
 }
 
+/// Returns the target of the symlink at `path`.
+///
+/// An error is returned if `path` is not a symlink.
+///
+/// ```rust,no_run
+/// let target = latin::file::read_link("./link.txt");
+/// ```
+pub fn read_link<P: AsRef<Path>>(path: P) -> IoResult<PathBuf> {
+    fs_read_link(path)
+}
+
+/// Creates a symlink at `dst` pointing to `src`.
+///
+/// ```rust,no_run
+/// latin::file::symlink("./foo.txt", "./link-to-foo.txt");
+/// ```
+#[cfg(unix)]
+pub fn symlink<Sp: AsRef<Path>, Dp: AsRef<Path>>(src: Sp, dst: Dp) -> IoResult<()> {
+    ::std::os::unix::fs::symlink(src, dst)
+}
+
+/// Creates a symlink at `dst` pointing to `src`.
+///
+/// ```rust,no_run
+/// latin::file::symlink("./foo.txt", "./link-to-foo.txt");
+/// ```
+#[cfg(windows)]
+pub fn symlink<Sp: AsRef<Path>, Dp: AsRef<Path>>(src: Sp, dst: Dp) -> IoResult<()> {
+    let src = src.as_ref();
+    if try!(::std::fs::metadata(src)).is_dir() {
+        ::std::os::windows::fs::symlink_dir(src, dst)
+    } else {
+        ::std::os::windows::fs::symlink_file(src, dst)
+    }
+}
+
+/// Returns true if `path` is a symlink.
+///
+/// Unlike `exists`, this does not follow the link to check the target.
+///
+/// ```rust,no_run
+/// if latin::file::is_symlink("./link.txt") {
+///     // do stuff
+/// }
+/// ```
+pub fn is_symlink<P: AsRef<Path>>(path: P) -> bool {
+    symlink_metadata(path).map(|meta| meta.file_type().is_symlink()).unwrap_or(false)
+}
+
+/// Returns the size in bytes of the file at `path`.
+///
+/// ```rust,no_run
+/// let size = latin::file::size("foo.txt");
+/// ```
+pub fn size<P: AsRef<Path>>(path: P) -> IoResult<u64> {
+    Ok(try!(metadata(path)).len())
+}
+
+/// Returns the last modification time of the file at `path`.
+///
+/// ```rust,no_run
+/// let modified = latin::file::modified("foo.txt");
+/// ```
+pub fn modified<P: AsRef<Path>>(path: P) -> IoResult<SystemTime> {
+    try!(metadata(path)).modified()
+}
+
+/// Returns the last access time of the file at `path`.
+///
+/// ```rust,no_run
+/// let accessed = latin::file::accessed("foo.txt");
+/// ```
+pub fn accessed<P: AsRef<Path>>(path: P) -> IoResult<SystemTime> {
+    try!(metadata(path)).accessed()
+}
+
+/// Returns the creation time of the file at `path`.
+///
+/// ```rust,no_run
+/// let created = latin::file::created("foo.txt");
+/// ```
+pub fn created<P: AsRef<Path>>(path: P) -> IoResult<SystemTime> {
+    try!(metadata(path)).created()
+}
+
+/// Returns true if the file at `path` is read-only.
+///
+/// ```rust,no_run
+/// if latin::file::is_readonly("foo.txt").unwrap() {
+///     // do stuff
+/// }
+/// ```
+pub fn is_readonly<P: AsRef<Path>>(path: P) -> IoResult<bool> {
+    Ok(try!(metadata(path)).permissions().readonly())
+}
+
+/// Sets or clears the read-only flag on the file at `path`.
+///
+/// ```rust,no_run
+/// latin::file::set_readonly("foo.txt", true);
+/// ```
+pub fn set_readonly<P: AsRef<Path>>(path: P, readonly: bool) -> IoResult<()> {
+    let mut permissions = try!(metadata(&path)).permissions();
+    permissions.set_readonly(readonly);
+    ::std::fs::set_permissions(path, permissions)
+}
+
 /// Checks to see if the file at `path` has the file extension `ext`.
 pub fn has_extension<P: AsRef<Path>, S: AsRef<str>>(path: P, ext: S) -> bool {
     path.as_ref().extension()